@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::ops::Range;
 
 /// Possible errors that can occur during bencode parsing.
 #[derive(PartialEq, Eq, Debug)]
@@ -7,8 +9,16 @@ pub enum ParseError {
     InvalidByte(usize),
     /// Indicates the end of the stream was reached unexpectedly.
     UnexpectedEndOfStream,
-    /// Indicates the stream contained invalid UTF-8.
-    InvalidUtf8,
+    /// Indicates the stream had extra bytes after a complete bencode value.
+    TrailingData(usize),
+    /// Indicates a non-canonical integer or string length (leading zeros,
+    /// negative zero) was rejected by [`decode_strict`].
+    NonCanonicalInteger(usize),
+    /// Indicates a dictionary key was not strictly greater than the
+    /// previous key, as rejected by [`decode_strict`].
+    UnorderedDictKey(usize),
+    /// Indicates an integer literal did not fit in an `i64`.
+    IntegerOverflow(usize),
 }
 
 /// Represents a bencode value.
@@ -20,8 +30,9 @@ pub enum Bencode {
     Str(Vec<u8>),
     /// Represents a list of bencode values.
     List(Vec<Bencode>),
-    /// Represents a dictionary of bencode values.
-    Dict(HashMap<String, Bencode>),
+    /// Represents a dictionary of bencode values, keyed on raw bytes since
+    /// the bencode spec allows dictionary keys to be arbitrary byte strings.
+    Dict(BTreeMap<Vec<u8>, Bencode>),
 }
 
 impl Bencode {
@@ -66,22 +77,212 @@ impl Bencode {
     /// # Returns
     ///
     /// An `Option` containing the dictionary value or `None` if this is not a `Bencode::Dict`.
-    pub fn as_dict(&self) -> Option<&HashMap<String, Bencode>> {
+    pub fn as_dict(&self) -> Option<&BTreeMap<Vec<u8>, Bencode>> {
         match self {
             Bencode::Dict(d) => Some(d),
             _ => None,
         }
     }
+
+    /// Looks up a dictionary entry by a UTF-8 key, for the common case where
+    /// the caller knows the key is text (e.g. `"announce"`, `"info"`).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The UTF-8 key to look up.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the value if this is a `Bencode::Dict` and it
+    /// has an entry whose raw bytes match `key`.
+    pub fn get_str(&self, key: &str) -> Option<&Bencode> {
+        self.as_dict()?.get(key.as_bytes())
+    }
+
+    /// Serializes this value as canonical bencode into `out`.
+    ///
+    /// Dictionary entries are written in ascending lexicographic byte order,
+    /// as required by the bencode spec.
+    ///
+    /// # Arguments
+    ///
+    /// * `out` - The writer to serialize into.
+    pub fn serialize(&self, out: &mut dyn Write) -> io::Result<()> {
+        match self {
+            Bencode::Int(i) => write!(out, "i{}e", i),
+            Bencode::Str(s) => {
+                write!(out, "{}:", s.len())?;
+                out.write_all(s)
+            }
+            Bencode::List(l) => {
+                out.write_all(b"l")?;
+                for item in l {
+                    item.serialize(out)?;
+                }
+                out.write_all(b"e")
+            }
+            Bencode::Dict(d) => {
+                out.write_all(b"d")?;
+                // `BTreeMap<Vec<u8>, _>` already iterates in ascending byte
+                // order, which is what the bencode spec requires.
+                for (key, value) in d {
+                    write!(out, "{}:", key.len())?;
+                    out.write_all(key)?;
+                    value.serialize(out)?;
+                }
+                out.write_all(b"e")
+            }
+        }
+    }
 }
 
-struct Decoder<'a> {
+/// Low-level cursor over a bencode byte stream.
+///
+/// Every decoder variant (`Decoder`, `RefDecoder`, `SpanDecoder`) embeds one
+/// of these instead of re-implementing bounds-checked byte scanning and
+/// integer parsing itself; doing this scanning in one place means all three
+/// decoders stay in sync by construction.
+struct Cursor<'a> {
     stream: &'a [u8],
     pos: usize,
 }
 
+impl<'a> Cursor<'a> {
+    fn new(stream: &'a [u8]) -> Self {
+        Cursor { stream, pos: 0 }
+    }
+
+    /// Returns the byte at the current position without advancing, or
+    /// `UnexpectedEndOfStream` if `pos` is past the end of `stream`.
+    fn peek(&self) -> Result<u8, ParseError> {
+        self.stream
+            .get(self.pos)
+            .copied()
+            .ok_or(ParseError::UnexpectedEndOfStream)
+    }
+
+    /// Scans an optional `-` sign followed by zero or more ASCII digits,
+    /// stopping just before `stop_byte`. Returns whether a sign was seen and
+    /// the position the digit run started at.
+    fn scan_signed_digits(&mut self, stop_byte: u8) -> Result<(bool, usize), ParseError> {
+        let is_negative = self.peek()? == b'-';
+        if is_negative {
+            self.pos += 1;
+        }
+        let digits_start = self.pos;
+        while self.peek()? != stop_byte {
+            if self.peek()?.is_ascii_digit() {
+                self.pos += 1;
+            } else {
+                return Err(ParseError::InvalidByte(self.pos));
+            }
+        }
+        Ok((is_negative, digits_start))
+    }
+
+    /// Parses the digit run `stream[digits_start..digits_end]` (as scanned by
+    /// [`Cursor::scan_signed_digits`]) into an `i64`, applying `is_negative`.
+    ///
+    /// Slicing the digits and deferring to `str::parse` means a syntactically
+    /// valid but too-large literal reports `IntegerOverflow` instead of
+    /// panicking, unlike a hand-rolled `acc = acc * 10 + digit` loop. The
+    /// magnitude is parsed as `u64` rather than `i64`: `i64::MIN`'s magnitude
+    /// (`9223372036854775808`) is one past `i64::MAX` and would otherwise be
+    /// rejected as an overflow even though `i-9223372036854775808e` is a
+    /// perfectly valid, in-range bencode integer.
+    fn parse_signed_digits(
+        &self,
+        is_negative: bool,
+        digits_start: usize,
+        digits_end: usize,
+        start: usize,
+    ) -> Result<i64, ParseError> {
+        if digits_start == digits_end {
+            // Non-strict callers have always tolerated a digit-less `ie` as
+            // 0; strict callers reject it before ever calling this.
+            return Ok(0);
+        }
+        let text = std::str::from_utf8(&self.stream[digits_start..digits_end])
+            .map_err(|_| ParseError::InvalidByte(start))?;
+        let magnitude: u64 = text
+            .parse()
+            .map_err(|_| ParseError::IntegerOverflow(start))?;
+        if is_negative {
+            if magnitude == i64::MIN.unsigned_abs() {
+                Ok(i64::MIN)
+            } else {
+                i64::try_from(magnitude)
+                    .map(|v| -v)
+                    .map_err(|_| ParseError::IntegerOverflow(start))
+            }
+        } else {
+            i64::try_from(magnitude).map_err(|_| ParseError::IntegerOverflow(start))
+        }
+    }
+
+    /// Scans an unsigned decimal length prefix (the `N` in `N:...`), stopping
+    /// just before `stop_byte`. Returns the parsed length and the position
+    /// the digit run started at (so callers can check for leading zeros).
+    ///
+    /// Like [`Cursor::parse_signed_digits`], this slices the digit run and
+    /// defers to `str::parse` rather than accumulating with `size * 10 +
+    /// digit`: an untrusted, arbitrarily long length prefix would otherwise
+    /// panic on overflow in debug builds and silently wrap to a bogus length
+    /// in release builds.
+    fn scan_length_prefix(&mut self, stop_byte: u8) -> Result<(usize, usize), ParseError> {
+        let digits_start = self.pos;
+        while self.peek()? != stop_byte {
+            if self.peek()?.is_ascii_digit() {
+                self.pos += 1;
+            } else {
+                return Err(ParseError::InvalidByte(self.pos));
+            }
+        }
+        let digits_end = self.pos;
+        let size = if digits_start == digits_end {
+            0
+        } else {
+            let text = std::str::from_utf8(&self.stream[digits_start..digits_end])
+                .map_err(|_| ParseError::InvalidByte(digits_start))?;
+            text.parse::<usize>()
+                .map_err(|_| ParseError::IntegerOverflow(digits_start))?
+        };
+        Ok((size, digits_start))
+    }
+
+    /// Returns the `len` bytes starting at the current position, bounds-
+    /// checked against the stream, and advances past them.
+    fn take_bytes(&mut self, len: usize) -> Result<&'a [u8], ParseError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.stream.len())
+            .ok_or(ParseError::UnexpectedEndOfStream)?;
+        let s = &self.stream[self.pos..end];
+        self.pos = end;
+        Ok(s)
+    }
+}
+
+struct Decoder<'a> {
+    cursor: Cursor<'a>,
+    /// When set, rejects non-canonical encodings (see [`decode_strict`]).
+    strict: bool,
+}
+
 impl<'a> Decoder<'a> {
     pub fn new(stream: &'a [u8]) -> Self {
-        Decoder { stream, pos: 0 }
+        Decoder {
+            cursor: Cursor::new(stream),
+            strict: false,
+        }
+    }
+
+    pub fn new_strict(stream: &'a [u8]) -> Self {
+        Decoder {
+            cursor: Cursor::new(stream),
+            strict: true,
+        }
     }
 
     pub fn decode(&mut self) -> Result<Bencode, ParseError> {
@@ -89,103 +290,102 @@ impl<'a> Decoder<'a> {
     }
 
     fn parse(&mut self) -> Result<Bencode, ParseError> {
-        if self.pos >= self.stream.len() {
-            return Err(ParseError::UnexpectedEndOfStream);
-        }
-
-        let curr_byte = self.stream[self.pos];
-        match curr_byte {
+        match self.cursor.peek()? {
             b'd' => self.parse_dict(),
             b'l' => self.parse_list(),
             b'i' => self.parse_int(),
             b'0'..=b'9' => self.parse_str(),
-            _ => Err(ParseError::InvalidByte(self.pos)),
+            _ => Err(ParseError::InvalidByte(self.cursor.pos)),
         }
     }
 
     fn parse_list(&mut self) -> Result<Bencode, ParseError> {
         let mut list: Vec<Bencode> = Vec::new();
-        self.pos += 1; // Skip the 'l'
-        while self.stream[self.pos] != b'e' {
-            let parsed = self.parse()?;
-            list.push(parsed);
+        self.cursor.pos += 1; // Skip the 'l'
+        while self.cursor.peek()? != b'e' {
+            list.push(self.parse()?);
         }
-        self.pos += 1; // Skip the 'e'
+        self.cursor.pos += 1; // Skip the 'e'
         Ok(Bencode::List(list))
     }
 
     fn parse_dict(&mut self) -> Result<Bencode, ParseError> {
-        let mut dict: HashMap<String, Bencode> = HashMap::new();
-        self.pos += 1; // Skip the 'd'
-        while self.stream[self.pos] != b'e' {
+        let mut dict: BTreeMap<Vec<u8>, Bencode> = BTreeMap::new();
+        self.cursor.pos += 1; // Skip the 'd'
+        let mut prev_key: Option<Vec<u8>> = None;
+        while self.cursor.peek()? != b'e' {
+            let key_start = self.cursor.pos;
             let key = match self.parse_str()? {
                 Bencode::Str(s) => s,
-                _ => return Err(ParseError::InvalidByte(self.pos)),
+                _ => return Err(ParseError::InvalidByte(self.cursor.pos)),
             };
+            if self.strict {
+                let is_ordered = match &prev_key {
+                    Some(prev) => key > *prev,
+                    None => true,
+                };
+                if !is_ordered {
+                    return Err(ParseError::UnorderedDictKey(key_start));
+                }
+                prev_key = Some(key.clone());
+            }
             let value = self.parse()?;
-            let key = match String::from_utf8(key) {
-                Ok(s) => s,
-                Err(_) => return Err(ParseError::InvalidUtf8),
-            };
             dict.insert(key, value);
         }
-        self.pos += 1; // Skip the 'e'
+        self.cursor.pos += 1; // Skip the 'e'
         Ok(Bencode::Dict(dict))
     }
 
     fn parse_str(&mut self) -> Result<Bencode, ParseError> {
-        let mut str_size: usize = 0;
-        while self.stream[self.pos] != b':' {
-            if self.stream[self.pos].is_ascii_digit() {
-                str_size = str_size * 10 + (self.stream[self.pos] - b'0') as usize;
-            } else {
-                return Err(ParseError::InvalidByte(self.pos));
-            }
-            self.pos += 1;
-        }
-        self.pos += 1;
+        let len_start = self.cursor.pos;
+        let (str_size, digits_start) = self.cursor.scan_length_prefix(b':')?;
 
-        if self.pos + str_size > self.stream.len() {
-            return Err(ParseError::UnexpectedEndOfStream);
+        if self.strict
+            && self.cursor.pos - digits_start > 1
+            && self.cursor.stream[digits_start] == b'0'
+        {
+            return Err(ParseError::NonCanonicalInteger(len_start));
         }
 
-        let s = &self.stream[self.pos..self.pos + str_size];
-        self.pos += str_size;
-
+        self.cursor.pos += 1; // Skip the ':'
+        let s = self.cursor.take_bytes(str_size)?;
         Ok(Bencode::Str(s.to_vec()))
     }
 
     fn parse_int(&mut self) -> Result<Bencode, ParseError> {
-        self.pos += 1; // Skip the 'i'
+        let start = self.cursor.pos;
+        self.cursor.pos += 1; // Skip the 'i'
 
-        let mut is_negative = false;
-        if self.stream[self.pos] == b'-' {
-            is_negative = true;
-            self.pos += 1;
-        }
+        let (is_negative, digits_start) = self.cursor.scan_signed_digits(b'e')?;
+        let digits_end = self.cursor.pos;
+        let digit_count = digits_end - digits_start;
 
-        let mut curr_int: i64 = 0;
-        while self.stream[self.pos] != b'e' {
-            if self.stream[self.pos].is_ascii_digit() {
-                curr_int = curr_int * 10 + (self.stream[self.pos] - b'0') as i64;
-            } else {
-                return Err(ParseError::InvalidByte(self.pos));
+        if self.strict {
+            let has_leading_zero = digit_count > 1 && self.cursor.stream[digits_start] == b'0';
+            if digit_count == 0 || has_leading_zero {
+                return Err(ParseError::NonCanonicalInteger(start));
             }
-            self.pos += 1;
         }
 
-        self.pos += 1;
+        let curr_int = self
+            .cursor
+            .parse_signed_digits(is_negative, digits_start, digits_end, start)?;
 
-        if is_negative {
-            curr_int = -curr_int;
+        if self.strict && is_negative && curr_int == 0 {
+            return Err(ParseError::NonCanonicalInteger(start));
         }
 
+        self.cursor.pos += 1; // Skip the 'e'
+
         Ok(Bencode::Int(curr_int))
     }
 }
 
 /// Decodes a bencode-encoded byte stream.
 ///
+/// Returns an error if `stream` contains trailing bytes after the decoded
+/// value; use [`decode_prefix`] to decode one value out of a longer buffer.
+///
 /// # Arguments
 ///
 /// * `stream` - A byte slice containing the bencode-encoded data.
@@ -194,7 +394,481 @@ impl<'a> Decoder<'a> {
 ///
 /// A `Result` containing the decoded `Bencode` value or a `ParseError`.
 pub fn decode(stream: &[u8]) -> Result<Bencode, ParseError> {
+    let (value, consumed) = decode_prefix(stream)?;
+    if consumed != stream.len() {
+        return Err(ParseError::TrailingData(consumed));
+    }
+    Ok(value)
+}
+
+/// Decodes a single bencode value from the start of `stream`, returning it
+/// along with the number of bytes consumed.
+///
+/// This allows callers to parse several concatenated bencode values out of
+/// one buffer (e.g. framed messages read off a socket) by feeding
+/// `&stream[consumed..]` back in on the next call.
+///
+/// # Arguments
+///
+/// * `stream` - A byte slice containing the bencode-encoded data.
+///
+/// # Returns
+///
+/// A `Result` containing the decoded `Bencode` value and the number of bytes
+/// consumed, or a `ParseError`.
+pub fn decode_prefix(stream: &[u8]) -> Result<(Bencode, usize), ParseError> {
     let mut decoder = Decoder::new(stream);
+    let value = decoder.decode()?;
+    Ok((value, decoder.cursor.pos))
+}
+
+/// Decodes a bencode-encoded byte stream, rejecting non-canonical encodings.
+///
+/// Unlike [`decode`], this rejects integers and string lengths with leading
+/// zeros, negative zero (`i-0e`), and dictionaries whose keys are not
+/// strictly ascending and unique by raw byte order. BitTorrent requires
+/// canonical bencode for info-hash stability, so this lets callers detect
+/// tampered or malformed metadata.
+///
+/// # Arguments
+///
+/// * `stream` - A byte slice containing the bencode-encoded data.
+///
+/// # Returns
+///
+/// A `Result` containing the decoded `Bencode` value or a `ParseError`.
+pub fn decode_strict(stream: &[u8]) -> Result<Bencode, ParseError> {
+    let mut decoder = Decoder::new_strict(stream);
+    let value = decoder.decode()?;
+    if decoder.cursor.pos != stream.len() {
+        return Err(ParseError::TrailingData(decoder.cursor.pos));
+    }
+    Ok(value)
+}
+
+/// Encodes a `Bencode` value into its canonical byte representation.
+///
+/// # Arguments
+///
+/// * `value` - The `Bencode` value to encode.
+///
+/// # Returns
+///
+/// A `Vec<u8>` containing the bencode-encoded bytes.
+pub fn encode(value: &Bencode) -> Vec<u8> {
+    let mut out = Vec::new();
+    value.serialize(&mut out).expect("writing to a Vec<u8> is infallible");
+    out
+}
+
+/// A zero-copy view over a bencode-encoded buffer.
+///
+/// Unlike [`Bencode`], which copies every string into an owned `Vec<u8>`,
+/// `BencodeRef` borrows slices directly from the input buffer. This avoids
+/// copying large blobs (e.g. the `pieces` field of a `.torrent` file) when
+/// the caller only needs to inspect the structure.
+#[derive(PartialEq, Eq, Debug)]
+pub enum BencodeRef<'a> {
+    /// Represents an integer value.
+    Int(i64),
+    /// Represents a string value borrowed from the input buffer.
+    Str(&'a [u8]),
+    /// Represents a list of bencode values.
+    List(Vec<BencodeRef<'a>>),
+    /// Represents a dictionary of bencode values, keyed on raw bytes borrowed
+    /// from the input buffer.
+    Dict(BTreeMap<&'a [u8], BencodeRef<'a>>),
+}
+
+impl<'a> BencodeRef<'a> {
+    /// Returns the integer value if this is a `BencodeRef::Int`.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the integer value or `None` if this is not a `BencodeRef::Int`.
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            BencodeRef::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns the string value if this is a `BencodeRef::Str`.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the borrowed bytes or `None` if this is not a `BencodeRef::Str`.
+    pub fn as_bytes(&self) -> Option<&'a [u8]> {
+        match self {
+            BencodeRef::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the list value if this is a `BencodeRef::List`.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the list value or `None` if this is not a `BencodeRef::List`.
+    pub fn as_list(&self) -> Option<&Vec<BencodeRef<'a>>> {
+        match self {
+            BencodeRef::List(l) => Some(l),
+            _ => None,
+        }
+    }
+
+    /// Returns the dictionary value if this is a `BencodeRef::Dict`.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the dictionary value or `None` if this is not a `BencodeRef::Dict`.
+    pub fn as_dict(&self) -> Option<&BTreeMap<&'a [u8], BencodeRef<'a>>> {
+        match self {
+            BencodeRef::Dict(d) => Some(d),
+            _ => None,
+        }
+    }
+
+    /// Looks up a dictionary entry by a UTF-8 key, for the common case where
+    /// the caller knows the key is text (e.g. `"announce"`, `"info"`).
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The UTF-8 key to look up.
+    ///
+    /// # Returns
+    ///
+    /// An `Option` containing the value if this is a `BencodeRef::Dict` and it
+    /// has an entry whose raw bytes match `key`.
+    pub fn get_str(&self, key: &str) -> Option<&BencodeRef<'a>> {
+        self.as_dict()?.get(key.as_bytes())
+    }
+
+    /// Produces an owned [`Bencode`] value by copying every borrowed slice.
+    ///
+    /// # Returns
+    ///
+    /// An owned `Bencode` with the same structure and contents as this view.
+    pub fn to_owned(&self) -> Bencode {
+        match self {
+            BencodeRef::Int(i) => Bencode::Int(*i),
+            BencodeRef::Str(s) => Bencode::Str(s.to_vec()),
+            BencodeRef::List(l) => Bencode::List(l.iter().map(BencodeRef::to_owned).collect()),
+            BencodeRef::Dict(d) => Bencode::Dict(
+                d.iter()
+                    .map(|(k, v)| (k.to_vec(), v.to_owned()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+struct RefDecoder<'a> {
+    cursor: Cursor<'a>,
+}
+
+impl<'a> RefDecoder<'a> {
+    pub fn new(stream: &'a [u8]) -> Self {
+        RefDecoder {
+            cursor: Cursor::new(stream),
+        }
+    }
+
+    pub fn decode(&mut self) -> Result<BencodeRef<'a>, ParseError> {
+        self.parse()
+    }
+
+    fn parse(&mut self) -> Result<BencodeRef<'a>, ParseError> {
+        match self.cursor.peek()? {
+            b'd' => self.parse_dict(),
+            b'l' => self.parse_list(),
+            b'i' => self.parse_int(),
+            b'0'..=b'9' => self.parse_str(),
+            _ => Err(ParseError::InvalidByte(self.cursor.pos)),
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<BencodeRef<'a>, ParseError> {
+        let mut list: Vec<BencodeRef<'a>> = Vec::new();
+        self.cursor.pos += 1; // Skip the 'l'
+        while self.cursor.peek()? != b'e' {
+            list.push(self.parse()?);
+        }
+        self.cursor.pos += 1; // Skip the 'e'
+        Ok(BencodeRef::List(list))
+    }
+
+    fn parse_dict(&mut self) -> Result<BencodeRef<'a>, ParseError> {
+        let mut dict: BTreeMap<&'a [u8], BencodeRef<'a>> = BTreeMap::new();
+        self.cursor.pos += 1; // Skip the 'd'
+        while self.cursor.peek()? != b'e' {
+            let key = match self.parse_str()? {
+                BencodeRef::Str(s) => s,
+                _ => return Err(ParseError::InvalidByte(self.cursor.pos)),
+            };
+            let value = self.parse()?;
+            dict.insert(key, value);
+        }
+        self.cursor.pos += 1; // Skip the 'e'
+        Ok(BencodeRef::Dict(dict))
+    }
+
+    fn parse_str(&mut self) -> Result<BencodeRef<'a>, ParseError> {
+        let (str_size, _digits_start) = self.cursor.scan_length_prefix(b':')?;
+        self.cursor.pos += 1; // Skip the ':'
+        let s = self.cursor.take_bytes(str_size)?;
+        Ok(BencodeRef::Str(s))
+    }
+
+    fn parse_int(&mut self) -> Result<BencodeRef<'a>, ParseError> {
+        let start = self.cursor.pos;
+        self.cursor.pos += 1; // Skip the 'i'
+
+        let (is_negative, digits_start) = self.cursor.scan_signed_digits(b'e')?;
+        let digits_end = self.cursor.pos;
+
+        let curr_int =
+            self.cursor
+                .parse_signed_digits(is_negative, digits_start, digits_end, start)?;
+
+        self.cursor.pos += 1; // Skip the 'e'
+
+        Ok(BencodeRef::Int(curr_int))
+    }
+}
+
+/// Decodes a bencode-encoded byte stream as a zero-copy [`BencodeRef`] that
+/// borrows strings and dictionary keys from `stream` instead of copying them.
+///
+/// # Arguments
+///
+/// * `stream` - A byte slice containing the bencode-encoded data.
+///
+/// # Returns
+///
+/// A `Result` containing the decoded `BencodeRef` value or a `ParseError`.
+pub fn decode_ref(stream: &[u8]) -> Result<BencodeRef<'_>, ParseError> {
+    let mut decoder = RefDecoder::new(stream);
+    decoder.decode()
+}
+
+/// A decoded bencode value in which every node (including nested lists and
+/// dictionaries) remembers the exact byte range `[start, end)` it occupied
+/// in the source stream.
+///
+/// This is what the `torrent` module builds on to locate the raw, unparsed
+/// `info` dictionary of a `.torrent` file for info-hash computation, without
+/// re-serializing it (re-encoding could produce different bytes than the
+/// original if the source wasn't already canonical).
+#[derive(PartialEq, Eq, Debug)]
+pub enum BencodeSpanned {
+    /// Represents an integer value.
+    Int(i64, Range<usize>),
+    /// Represents a string value.
+    Str(Vec<u8>, Range<usize>),
+    /// Represents a list of bencode values.
+    List(Vec<BencodeSpanned>, Range<usize>),
+    /// Represents a dictionary of bencode values.
+    Dict(BTreeMap<Vec<u8>, BencodeSpanned>, Range<usize>),
+}
+
+impl BencodeSpanned {
+    /// Returns the byte range `[start, end)` this value occupied in the
+    /// stream it was decoded from.
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            BencodeSpanned::Int(_, span) => span.clone(),
+            BencodeSpanned::Str(_, span) => span.clone(),
+            BencodeSpanned::List(_, span) => span.clone(),
+            BencodeSpanned::Dict(_, span) => span.clone(),
+        }
+    }
+
+    /// Returns the integer value if this is a `BencodeSpanned::Int`.
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            BencodeSpanned::Int(i, _) => Some(*i),
+            _ => None,
+        }
+    }
+
+    /// Returns the string value if this is a `BencodeSpanned::Str`.
+    pub fn as_bytes(&self) -> Option<&Vec<u8>> {
+        match self {
+            BencodeSpanned::Str(s, _) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Returns the list value if this is a `BencodeSpanned::List`.
+    pub fn as_list(&self) -> Option<&Vec<BencodeSpanned>> {
+        match self {
+            BencodeSpanned::List(l, _) => Some(l),
+            _ => None,
+        }
+    }
+
+    /// Returns the dictionary value if this is a `BencodeSpanned::Dict`.
+    pub fn as_dict(&self) -> Option<&BTreeMap<Vec<u8>, BencodeSpanned>> {
+        match self {
+            BencodeSpanned::Dict(d, _) => Some(d),
+            _ => None,
+        }
+    }
+
+    /// Looks up a dictionary entry by a UTF-8 key, for the common case where
+    /// the caller knows the key is text (e.g. `"announce"`, `"info"`).
+    pub fn get_str(&self, key: &str) -> Option<&BencodeSpanned> {
+        self.as_dict()?.get(key.as_bytes())
+    }
+
+    /// Discards span information, producing a plain owned [`Bencode`] value.
+    pub fn to_owned(&self) -> Bencode {
+        match self {
+            BencodeSpanned::Int(i, _) => Bencode::Int(*i),
+            BencodeSpanned::Str(s, _) => Bencode::Str(s.clone()),
+            BencodeSpanned::List(l, _) => {
+                Bencode::List(l.iter().map(BencodeSpanned::to_owned).collect())
+            }
+            BencodeSpanned::Dict(d, _) => Bencode::Dict(
+                d.iter()
+                    .map(|(k, v)| (k.clone(), v.to_owned()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+struct SpanDecoder<'a> {
+    cursor: Cursor<'a>,
+    /// When set, rejects unordered or duplicate dictionary keys, the same
+    /// way `Decoder`'s strict mode does (see [`decode_spanned_strict`]).
+    strict: bool,
+}
+
+impl<'a> SpanDecoder<'a> {
+    pub fn new(stream: &'a [u8]) -> Self {
+        SpanDecoder {
+            cursor: Cursor::new(stream),
+            strict: false,
+        }
+    }
+
+    pub fn new_strict(stream: &'a [u8]) -> Self {
+        SpanDecoder {
+            cursor: Cursor::new(stream),
+            strict: true,
+        }
+    }
+
+    pub fn decode(&mut self) -> Result<BencodeSpanned, ParseError> {
+        self.parse()
+    }
+
+    fn parse(&mut self) -> Result<BencodeSpanned, ParseError> {
+        let start = self.cursor.pos;
+        match self.cursor.peek()? {
+            b'd' => self.parse_dict(start),
+            b'l' => self.parse_list(start),
+            b'i' => self.parse_int(start),
+            b'0'..=b'9' => self.parse_str(start),
+            _ => Err(ParseError::InvalidByte(self.cursor.pos)),
+        }
+    }
+
+    fn parse_list(&mut self, start: usize) -> Result<BencodeSpanned, ParseError> {
+        let mut list: Vec<BencodeSpanned> = Vec::new();
+        self.cursor.pos += 1; // Skip the 'l'
+        while self.cursor.peek()? != b'e' {
+            list.push(self.parse()?);
+        }
+        self.cursor.pos += 1; // Skip the 'e'
+        Ok(BencodeSpanned::List(list, start..self.cursor.pos))
+    }
+
+    fn parse_dict(&mut self, start: usize) -> Result<BencodeSpanned, ParseError> {
+        let mut dict: BTreeMap<Vec<u8>, BencodeSpanned> = BTreeMap::new();
+        self.cursor.pos += 1; // Skip the 'd'
+        let mut prev_key: Option<Vec<u8>> = None;
+        while self.cursor.peek()? != b'e' {
+            let key_start = self.cursor.pos;
+            let key = match self.parse_str(key_start)? {
+                BencodeSpanned::Str(s, _) => s,
+                _ => return Err(ParseError::InvalidByte(self.cursor.pos)),
+            };
+            if self.strict {
+                let is_ordered = match &prev_key {
+                    Some(prev) => key > *prev,
+                    None => true,
+                };
+                if !is_ordered {
+                    return Err(ParseError::UnorderedDictKey(key_start));
+                }
+                prev_key = Some(key.clone());
+            }
+            let value = self.parse()?;
+            dict.insert(key, value);
+        }
+        self.cursor.pos += 1; // Skip the 'e'
+        Ok(BencodeSpanned::Dict(dict, start..self.cursor.pos))
+    }
+
+    fn parse_str(&mut self, start: usize) -> Result<BencodeSpanned, ParseError> {
+        let (str_size, _digits_start) = self.cursor.scan_length_prefix(b':')?;
+        self.cursor.pos += 1; // Skip the ':'
+        let s = self.cursor.take_bytes(str_size)?;
+        Ok(BencodeSpanned::Str(s.to_vec(), start..self.cursor.pos))
+    }
+
+    fn parse_int(&mut self, start: usize) -> Result<BencodeSpanned, ParseError> {
+        self.cursor.pos += 1; // Skip the 'i'
+
+        let (is_negative, digits_start) = self.cursor.scan_signed_digits(b'e')?;
+        let digits_end = self.cursor.pos;
+        self.cursor.pos += 1; // Skip the 'e'
+
+        let value = self
+            .cursor
+            .parse_signed_digits(is_negative, digits_start, digits_end, start)?;
+
+        Ok(BencodeSpanned::Int(value, start..self.cursor.pos))
+    }
+}
+
+/// Decodes a bencode-encoded byte stream, recording the exact byte range
+/// `[start, end)` of every value (including nested lists and dictionaries)
+/// within `stream`.
+///
+/// # Arguments
+///
+/// * `stream` - A byte slice containing the bencode-encoded data.
+///
+/// # Returns
+///
+/// A `Result` containing the decoded `BencodeSpanned` value or a `ParseError`.
+pub fn decode_spanned(stream: &[u8]) -> Result<BencodeSpanned, ParseError> {
+    let mut decoder = SpanDecoder::new(stream);
+    decoder.decode()
+}
+
+/// Decodes a bencode-encoded byte stream like [`decode_spanned`], but
+/// rejects dictionaries whose keys are not strictly ascending and unique by
+/// raw byte order.
+///
+/// Two parsers that disagree on which value wins for a duplicate key would
+/// read different metadata out of the same bytes, and so under the same
+/// info-hash; callers that need a stable hash over untrusted metadata (e.g.
+/// the `torrent` module) should use this instead of [`decode_spanned`].
+///
+/// # Arguments
+///
+/// * `stream` - A byte slice containing the bencode-encoded data.
+///
+/// # Returns
+///
+/// A `Result` containing the decoded `BencodeSpanned` value or a `ParseError`.
+pub fn decode_spanned_strict(stream: &[u8]) -> Result<BencodeSpanned, ParseError> {
+    let mut decoder = SpanDecoder::new_strict(stream);
     decoder.decode()
 }
 
@@ -218,6 +892,13 @@ mod tests {
         assert_eq!(result, Bencode::Str("spam".into()));
     }
 
+    #[test]
+    fn test_decode_oversized_str_len_is_an_error_not_a_panic() {
+        let oversized = b"99999999999999999999999999999999999999:abc";
+        assert_eq!(decode(oversized), Err(ParseError::IntegerOverflow(0)));
+        assert_eq!(decode_ref(oversized), Err(ParseError::IntegerOverflow(0)));
+    }
+
     #[test]
     fn test_decode_invalid_str() {
         let invalid_utf8: Vec<u8> = vec![0xF0, 0x28, 0x8C, 0xBC];
@@ -247,6 +928,36 @@ mod tests {
         assert_eq!(result, Err(ParseError::InvalidByte(1)));
     }
 
+    #[test]
+    fn test_decode_empty_int_is_lenient_zero() {
+        let result = decode(b"ie").unwrap();
+        assert_eq!(result, Bencode::Int(0));
+    }
+
+    #[test]
+    fn test_decode_int_overflow_is_an_error_not_a_panic() {
+        let overflowing = b"i99999999999999999999999999999999999999e";
+        assert_eq!(decode(overflowing), Err(ParseError::IntegerOverflow(0)));
+        assert_eq!(
+            decode_ref(overflowing),
+            Err(ParseError::IntegerOverflow(0))
+        );
+    }
+
+    #[test]
+    fn test_decode_i64_min_is_not_an_overflow() {
+        // i64::MIN's magnitude (9223372036854775808) is one past i64::MAX,
+        // but the literal itself is perfectly in-range.
+        let result = decode(b"i-9223372036854775808e").unwrap();
+        assert_eq!(result, Bencode::Int(i64::MIN));
+    }
+
+    #[test]
+    fn test_decode_one_past_i64_min_is_an_overflow() {
+        let result = decode(b"i-9223372036854775809e");
+        assert_eq!(result, Err(ParseError::IntegerOverflow(0)));
+    }
+
     #[test]
     fn test_decode_list() {
         let mut decoder = Decoder::new(b"l4:spam4:eggse");
@@ -264,12 +975,253 @@ mod tests {
     fn test_decode_dict() {
         let mut decoder = Decoder::new(b"d3:cow3:moo4:spam4:eggse");
         let result = decoder.decode().unwrap();
-        let mut expected_dict = HashMap::new();
-        expected_dict.insert("cow".to_string(), Bencode::Str("moo".into()));
-        expected_dict.insert("spam".to_string(), Bencode::Str("eggs".into()));
+        let mut expected_dict = BTreeMap::new();
+        expected_dict.insert(b"cow".to_vec(), Bencode::Str("moo".into()));
+        expected_dict.insert(b"spam".to_vec(), Bencode::Str("eggs".into()));
         assert_eq!(result, Bencode::Dict(expected_dict));
     }
 
+    #[test]
+    fn test_decode_dict_non_utf8_key() {
+        let non_utf8_key: Vec<u8> = vec![0xFF, 0xFE];
+        let mut stream = b"d2:".to_vec();
+        stream.extend_from_slice(&non_utf8_key);
+        stream.extend_from_slice(b"3:mooe");
+        let mut decoder = Decoder::new(&stream);
+        let result = decoder.decode().unwrap();
+        let dict = result.as_dict().unwrap();
+        assert_eq!(dict.get(non_utf8_key.as_slice()), Some(&Bencode::Str("moo".into())));
+    }
+
+    #[test]
+    fn test_decode_ref_str() {
+        let result = decode_ref(b"4:spam").unwrap();
+        assert_eq!(result, BencodeRef::Str(b"spam"));
+    }
+
+    #[test]
+    fn test_decode_ref_int() {
+        let result = decode_ref(b"i42e").unwrap();
+        assert_eq!(result, BencodeRef::Int(42));
+    }
+
+    #[test]
+    fn test_decode_ref_list() {
+        let result = decode_ref(b"l4:spam4:eggse").unwrap();
+        assert_eq!(
+            result,
+            BencodeRef::List(vec![BencodeRef::Str(b"spam"), BencodeRef::Str(b"eggs")])
+        );
+    }
+
+    #[test]
+    fn test_decode_ref_dict() {
+        let result = decode_ref(b"d3:cow3:moo4:spam4:eggse").unwrap();
+        let mut expected_dict = BTreeMap::new();
+        expected_dict.insert(b"cow".as_slice(), BencodeRef::Str(b"moo"));
+        expected_dict.insert(b"spam".as_slice(), BencodeRef::Str(b"eggs"));
+        assert_eq!(result, BencodeRef::Dict(expected_dict));
+    }
+
+    #[test]
+    fn test_decode_ref_borrows_from_input() {
+        let stream = b"4:spam".to_vec();
+        let result = decode_ref(&stream).unwrap();
+        // The returned slice should point into `stream`, not a copy of it.
+        assert_eq!(result.as_bytes().unwrap().as_ptr(), stream[2..].as_ptr());
+    }
+
+    #[test]
+    fn test_decode_ref_to_owned() {
+        let reference = decode_ref(b"d3:cow3:moo4:spam4:eggse").unwrap();
+        let owned = reference.to_owned();
+        assert_eq!(owned, decode(b"d3:cow3:moo4:spam4:eggse").unwrap());
+    }
+
+    #[test]
+    fn test_encode_str() {
+        let encoded = encode(&Bencode::Str("spam".into()));
+        assert_eq!(encoded, b"4:spam");
+    }
+
+    #[test]
+    fn test_encode_int() {
+        let encoded = encode(&Bencode::Int(42));
+        assert_eq!(encoded, b"i42e");
+    }
+
+    #[test]
+    fn test_encode_negative_int() {
+        let encoded = encode(&Bencode::Int(-42));
+        assert_eq!(encoded, b"i-42e");
+    }
+
+    #[test]
+    fn test_encode_list() {
+        let encoded = encode(&Bencode::List(vec![
+            Bencode::Str("spam".into()),
+            Bencode::Str("eggs".into()),
+        ]));
+        assert_eq!(encoded, b"l4:spam4:eggse");
+    }
+
+    #[test]
+    fn test_encode_dict_sorts_keys() {
+        let mut dict = BTreeMap::new();
+        dict.insert(b"spam".to_vec(), Bencode::Str("eggs".into()));
+        dict.insert(b"cow".to_vec(), Bencode::Str("moo".into()));
+        let encoded = encode(&Bencode::Dict(dict));
+        assert_eq!(encoded, b"d3:cow3:moo4:spam4:eggse");
+    }
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        let original = decode(b"d3:cow3:moo4:spam4:eggse").unwrap();
+        let encoded = encode(&original);
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(original, decoded);
+    }
+
+    #[test]
+    fn test_decode_prefix_returns_bytes_consumed() {
+        let (value, consumed) = decode_prefix(b"4:spam").unwrap();
+        assert_eq!(value, Bencode::Str("spam".into()));
+        assert_eq!(consumed, 6);
+    }
+
+    #[test]
+    fn test_decode_prefix_concatenated_values() {
+        let stream = b"4:spami42e";
+        let (first, consumed) = decode_prefix(stream).unwrap();
+        assert_eq!(first, Bencode::Str("spam".into()));
+        let (second, consumed2) = decode_prefix(&stream[consumed..]).unwrap();
+        assert_eq!(second, Bencode::Int(42));
+        assert_eq!(consumed + consumed2, stream.len());
+    }
+
+    #[test]
+    fn test_decode_rejects_trailing_data() {
+        let result = decode(b"4:spamgarbage");
+        assert_eq!(result, Err(ParseError::TrailingData(6)));
+    }
+
+    #[test]
+    fn test_decode_truncated_input_never_panics() {
+        // Every proper prefix of a valid document should return an error
+        // rather than panicking on an out-of-bounds slice index.
+        let documents: &[&[u8]] = &[
+            b"i42e",
+            b"4:spam",
+            b"l4:spam4:eggse",
+            b"d3:cow3:moo4:spam4:eggse",
+            b"i-42e",
+        ];
+        for document in documents {
+            for len in 0..document.len() {
+                let truncated = &document[..len];
+                assert!(decode(truncated).is_err());
+                assert!(decode_ref(truncated).is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_strict_accepts_canonical_input() {
+        let result = decode_strict(b"d3:cow3:moo4:spam4:eggse").unwrap();
+        assert_eq!(result, decode(b"d3:cow3:moo4:spam4:eggse").unwrap());
+    }
+
+    #[test]
+    fn test_decode_strict_rejects_leading_zero_int() {
+        let result = decode_strict(b"i03e");
+        assert_eq!(result, Err(ParseError::NonCanonicalInteger(0)));
+    }
+
+    #[test]
+    fn test_decode_strict_rejects_negative_zero() {
+        let result = decode_strict(b"i-0e");
+        assert_eq!(result, Err(ParseError::NonCanonicalInteger(0)));
+    }
+
+    #[test]
+    fn test_decode_strict_rejects_empty_int() {
+        let result = decode_strict(b"ie");
+        assert_eq!(result, Err(ParseError::NonCanonicalInteger(0)));
+    }
+
+    #[test]
+    fn test_decode_strict_rejects_leading_zero_str_len() {
+        let result = decode_strict(b"01:a");
+        assert_eq!(result, Err(ParseError::NonCanonicalInteger(0)));
+    }
+
+    #[test]
+    fn test_decode_strict_rejects_unordered_dict_keys() {
+        let result = decode_strict(b"d4:spam4:eggs3:cow3:mooe");
+        assert_eq!(result, Err(ParseError::UnorderedDictKey(13)));
+    }
+
+    #[test]
+    fn test_decode_strict_rejects_duplicate_dict_keys() {
+        let result = decode_strict(b"d3:cow3:moo3:cow3:mooe");
+        assert_eq!(result, Err(ParseError::UnorderedDictKey(11)));
+    }
+
+    #[test]
+    fn test_decode_strict_allows_valid_negative_int() {
+        let result = decode_strict(b"i-42e").unwrap();
+        assert_eq!(result, Bencode::Int(-42));
+    }
+
+    #[test]
+    fn test_decode_spanned_top_level_span_covers_whole_input() {
+        let stream = b"d3:cow3:moo4:spam4:eggse";
+        let result = decode_spanned(stream).unwrap();
+        assert_eq!(result.span(), 0..stream.len());
+    }
+
+    #[test]
+    fn test_decode_spanned_nested_value_span() {
+        // d4:info         d4:name4:spam5:piece5:helloe       e
+        // ^0              ^7                            ^35 ^36
+        let stream = b"d4:infod4:name4:spam5:piece5:helloee";
+        let result = decode_spanned(stream).unwrap();
+        let info = result.get_str("info").unwrap();
+        assert_eq!(info.span(), 7..35);
+        assert_eq!(info.to_owned(), decode(&stream[info.span()]).unwrap());
+    }
+
+    #[test]
+    fn test_decode_spanned_to_owned_matches_decode() {
+        let stream = b"d3:cow3:moo4:spam4:eggse";
+        let spanned = decode_spanned(stream).unwrap();
+        assert_eq!(spanned.to_owned(), decode(stream).unwrap());
+    }
+
+    #[test]
+    fn test_decode_spanned_allows_duplicate_keys_non_strict() {
+        // `decode_spanned` is lenient by default (last-key-wins), matching
+        // `decode`; `decode_spanned_strict` is what rejects this.
+        let result = decode_spanned(b"d4:name4:evil4:name4:good4:spam3:fooe").unwrap();
+        assert_eq!(
+            result.get_str("name").and_then(BencodeSpanned::as_bytes),
+            Some(&b"good".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_decode_spanned_strict_rejects_duplicate_keys() {
+        let result = decode_spanned_strict(b"d4:name4:evil4:name4:good4:spam3:fooe");
+        assert_eq!(result, Err(ParseError::UnorderedDictKey(13)));
+    }
+
+    #[test]
+    fn test_decode_spanned_strict_accepts_canonical_input() {
+        let stream = b"d3:cow3:moo4:spam4:eggse";
+        let result = decode_spanned_strict(stream).unwrap();
+        assert_eq!(result.to_owned(), decode(stream).unwrap());
+    }
+
     #[test]
     fn test_decode_torrent() {
         // Read the file into a byte vector
@@ -288,20 +1240,14 @@ mod tests {
             "info",
         ];
         for key in required_keys {
-            assert!(result.as_dict().unwrap().contains_key(key));
+            assert!(result.get_str(key).is_some());
         }
 
         // Check for required keys in the "info" dictionary
-        let info_dict = result
-            .as_dict()
-            .unwrap()
-            .get("info")
-            .unwrap()
-            .as_dict()
-            .unwrap();
+        let info_dict = result.get_str("info").unwrap().as_dict().unwrap();
         let required_keys = ["name", "piece length", "pieces"];
         for key in required_keys {
-            assert!(info_dict.contains_key(key));
+            assert!(info_dict.contains_key(key.as_bytes()));
         }
     }
 }
@@ -0,0 +1,2 @@
+pub mod bencoding;
+pub mod torrent;
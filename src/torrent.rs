@@ -0,0 +1,208 @@
+use std::ops::Range;
+
+use crate::bencoding::{self, BencodeSpanned, ParseError};
+
+/// Errors that can occur while extracting torrent metadata from a bencode
+/// document.
+#[derive(PartialEq, Eq, Debug)]
+pub enum TorrentError {
+    /// The underlying bencode document failed to parse.
+    Parse(ParseError),
+    /// The top-level dictionary had no `info` entry.
+    MissingInfoDict,
+    /// The `info` dictionary's `pieces` value was not a multiple of the
+    /// 20-byte SHA-1 hash size.
+    InvalidPiecesLength(usize),
+}
+
+impl From<ParseError> for TorrentError {
+    fn from(err: ParseError) -> Self {
+        TorrentError::Parse(err)
+    }
+}
+
+/// Parsed metadata from a `.torrent` file's top-level dictionary.
+///
+/// Alongside the commonly-needed fields, this tracks the raw byte range of
+/// the `info` dictionary so callers can hash it (e.g. with SHA-1) to derive
+/// the torrent's info-hash, without re-serializing it and risking mismatched
+/// bytes if the source wasn't already canonical bencode.
+#[derive(PartialEq, Eq, Debug)]
+pub struct TorrentInfo {
+    /// The tracker announce URL, if present.
+    pub announce: Option<String>,
+    /// The Unix timestamp the torrent was created, if present.
+    pub creation_date: Option<i64>,
+    /// The suggested file or directory name.
+    pub name: Option<String>,
+    /// The number of bytes in each piece.
+    pub piece_length: Option<i64>,
+    /// The SHA-1 hash of each piece, split out of the `pieces` blob.
+    pub pieces: Vec<[u8; 20]>,
+    info_span: Range<usize>,
+}
+
+impl TorrentInfo {
+    /// Parses torrent metadata out of the bencode-encoded bytes of a
+    /// `.torrent` file.
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - The raw bytes of a `.torrent` file.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the parsed `TorrentInfo` or a `TorrentError`.
+    pub fn parse(stream: &[u8]) -> Result<TorrentInfo, TorrentError> {
+        // Use the strict span decoder: this module exists to let callers
+        // derive a stable info-hash, so ambiguous metadata (duplicate or
+        // unordered dictionary keys, which different parsers could resolve
+        // differently) must be rejected rather than silently resolved
+        // last-key-wins.
+        let root = bencoding::decode_spanned_strict(stream)?;
+        let info = root.get_str("info").ok_or(TorrentError::MissingInfoDict)?;
+
+        let announce = root.get_str("announce").and_then(as_utf8);
+        let creation_date = root
+            .get_str("creation date")
+            .and_then(BencodeSpanned::as_int);
+        let name = info.get_str("name").and_then(as_utf8);
+        let piece_length = info.get_str("piece length").and_then(BencodeSpanned::as_int);
+        let pieces = match info.get_str("pieces").and_then(BencodeSpanned::as_bytes) {
+            Some(blob) => split_into_piece_hashes(blob)?,
+            None => Vec::new(),
+        };
+
+        Ok(TorrentInfo {
+            announce,
+            creation_date,
+            name,
+            piece_length,
+            pieces,
+            info_span: info.span(),
+        })
+    }
+
+    /// Returns the raw, unparsed bytes of the `info` dictionary.
+    ///
+    /// # Arguments
+    ///
+    /// * `stream` - The same byte slice originally passed to [`TorrentInfo::parse`].
+    ///
+    /// # Returns
+    ///
+    /// The raw `info` dictionary bytes, suitable for hashing to derive the
+    /// torrent's info-hash.
+    pub fn raw_info<'a>(&self, stream: &'a [u8]) -> &'a [u8] {
+        &stream[self.info_span.clone()]
+    }
+}
+
+fn as_utf8(value: &BencodeSpanned) -> Option<String> {
+    String::from_utf8(value.as_bytes()?.clone()).ok()
+}
+
+fn split_into_piece_hashes(blob: &[u8]) -> Result<Vec<[u8; 20]>, TorrentError> {
+    if !blob.len().is_multiple_of(20) {
+        return Err(TorrentError::InvalidPiecesLength(blob.len()));
+    }
+    Ok(blob
+        .chunks_exact(20)
+        .map(|chunk| {
+            let mut hash = [0u8; 20];
+            hash.copy_from_slice(chunk);
+            hash
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bencoding::{decode, encode, Bencode};
+    use std::collections::BTreeMap;
+
+    fn sample_torrent_bytes() -> Vec<u8> {
+        let mut info = BTreeMap::new();
+        info.insert(b"name".to_vec(), Bencode::Str(b"example.txt".to_vec()));
+        info.insert(b"piece length".to_vec(), Bencode::Int(16384));
+        info.insert(b"pieces".to_vec(), Bencode::Str(vec![0xAB; 40]));
+
+        let mut root = BTreeMap::new();
+        root.insert(
+            b"announce".to_vec(),
+            Bencode::Str(b"http://example.com/announce".to_vec()),
+        );
+        root.insert(b"creation date".to_vec(), Bencode::Int(1_234_567_890));
+        root.insert(b"info".to_vec(), Bencode::Dict(info));
+
+        encode(&Bencode::Dict(root))
+    }
+
+    #[test]
+    fn test_parse_extracts_top_level_fields() {
+        let stream = sample_torrent_bytes();
+        let torrent = TorrentInfo::parse(&stream).unwrap();
+        assert_eq!(
+            torrent.announce.as_deref(),
+            Some("http://example.com/announce")
+        );
+        assert_eq!(torrent.creation_date, Some(1_234_567_890));
+        assert_eq!(torrent.name.as_deref(), Some("example.txt"));
+        assert_eq!(torrent.piece_length, Some(16384));
+    }
+
+    #[test]
+    fn test_parse_splits_pieces_into_20_byte_hashes() {
+        let stream = sample_torrent_bytes();
+        let torrent = TorrentInfo::parse(&stream).unwrap();
+        assert_eq!(torrent.pieces.len(), 2);
+        assert_eq!(torrent.pieces[0], [0xAB; 20]);
+        assert_eq!(torrent.pieces[1], [0xAB; 20]);
+    }
+
+    #[test]
+    fn test_parse_missing_info_is_an_error() {
+        let stream = encode(&Bencode::Dict(BTreeMap::new()));
+        let result = TorrentInfo::parse(&stream);
+        assert_eq!(result, Err(TorrentError::MissingInfoDict));
+    }
+
+    #[test]
+    fn test_parse_rejects_pieces_not_a_multiple_of_20() {
+        let mut info = BTreeMap::new();
+        info.insert(b"pieces".to_vec(), Bencode::Str(vec![0xAB; 25]));
+        let mut root = BTreeMap::new();
+        root.insert(b"info".to_vec(), Bencode::Dict(info));
+        let stream = encode(&Bencode::Dict(root));
+
+        let result = TorrentInfo::parse(&stream);
+        assert_eq!(result, Err(TorrentError::InvalidPiecesLength(25)));
+    }
+
+    #[test]
+    fn test_raw_info_matches_the_encoded_info_dict() {
+        let stream = sample_torrent_bytes();
+        let torrent = TorrentInfo::parse(&stream).unwrap();
+        let decoded_info = decode(torrent.raw_info(&stream)).unwrap();
+
+        let top_level = decode(&stream).unwrap();
+        let expected_info = top_level.as_dict().unwrap().get(b"info".as_slice()).unwrap();
+        assert_eq!(&decoded_info, expected_info);
+    }
+
+    #[test]
+    fn test_parse_rejects_duplicate_dict_keys() {
+        // Hand-crafted (not built through `BTreeMap`, which can't hold
+        // duplicates): two different `name` values for the same key. Two
+        // consumers resolving this differently would compute the same
+        // info-hash for torrents with different names, so this must be
+        // rejected rather than silently resolved last-key-wins.
+        let stream = b"d4:infod4:name4:evil4:name4:good4:spam3:fooee".to_vec();
+        let result = TorrentInfo::parse(&stream);
+        assert!(matches!(
+            result,
+            Err(TorrentError::Parse(ParseError::UnorderedDictKey(_)))
+        ));
+    }
+}